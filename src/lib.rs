@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(test, deny(warnings))]
 #![deny(missing_docs)]
 
@@ -6,19 +7,33 @@
 extern crate num_traits;
 extern crate unreachable;
 
-use std::cmp::Ordering;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Rem,
+                RemAssign, Sub, SubAssign};
+use core::hash::{Hash, Hasher};
+use core::iter::{Product, Sum};
+use core::str::FromStr;
+use core::fmt;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::ops::{Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Neg, Rem,
-               RemAssign, Sub, SubAssign};
-use std::hash::{Hash, Hasher};
-use std::fmt;
+#[cfg(feature = "std")]
 use std::io;
-use std::mem;
 use unreachable::unreachable;
-use num_traits::{Bounded, Float, FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+use num_traits::{Bounded, FromPrimitive, Num, One, Signed, ToPrimitive, Zero};
+
+// When `std` is enabled the wrappers are usable with the full `Float` trait;
+// otherwise they are built on `FloatCore`, which provides everything the total
+// order and hashing machinery needs without pulling in `std`. Either way the
+// impl blocks below bound on the in-scope `Float` name.
+#[cfg(feature = "std")]
+pub use num_traits::Float;
+#[cfg(not(feature = "std"))]
+use num_traits::float::FloatCore as Float;
 
 // masks for the parts of the IEEE 754 float
 const SIGN_MASK: u64 = 0x8000000000000000u64;
+const SIGN_MASK_F32: u32 = 0x80000000u32;
 const EXP_MASK: u64 = 0x7ff0000000000000u64;
 const MAN_MASK: u64 = 0x000fffffffffffffu64;
 
@@ -41,6 +56,140 @@ impl<T: Float> OrderedFloat<T> {
     }
 }
 
+impl OrderedFloat<f64> {
+    /// Maps this value to a monotonic `u64` whose natural unsigned `<` ordering
+    /// is consistent with the total order defined by this crate: a value that
+    /// `Ord` ranks as less always gets a smaller key.
+    ///
+    /// This is the standard IEEE-754 total-order bit trick, suitable for
+    /// radix-sorting floats or for storing them as big-endian byte keys in a
+    /// key-value store. Because this crate sorts *every* NaN as *greatest*,
+    /// regardless of sign, the NaN sign bit is normalized away before encoding
+    /// so that all NaN bit patterns map to the top of the `u64` range.
+    ///
+    /// The key defines a *finer* order than [`Ord`](#impl-Ord): it is a total
+    /// order over raw bit patterns, so it distinguishes values that the crate
+    /// treats as equal. `-0.0` and `+0.0` get different keys, and distinct NaN
+    /// payloads get distinct keys, even though `Ord` ranks each such pair as
+    /// `Equal`. Normalize the input first if you need equal values to produce
+    /// equal keys.
+    pub fn total_cmp_key(&self) -> u64 {
+        // All NaN sort as greatest, so strip the sign bit to keep negative NaN
+        // out of the bottom of the range.
+        let bits = if self.0.is_nan() {
+            self.0.to_bits() & !SIGN_MASK
+        } else {
+            self.0.to_bits()
+        };
+        if bits & SIGN_MASK != 0 {
+            !bits
+        } else {
+            bits | SIGN_MASK
+        }
+    }
+
+    /// Reconstructs the value from a key produced by
+    /// [`total_cmp_key`](#method.total_cmp_key).
+    pub fn from_total_cmp_key(key: u64) -> Self {
+        let bits = if key & SIGN_MASK != 0 {
+            key & !SIGN_MASK
+        } else {
+            !key
+        };
+        OrderedFloat(f64::from_bits(bits))
+    }
+
+    /// Constructs a value directly from its raw IEEE-754 bits, preserving any
+    /// NaN payload.
+    pub fn from_bits(bits: u64) -> Self {
+        OrderedFloat(f64::from_bits(bits))
+    }
+
+    /// Returns the raw IEEE-754 bits of the wrapped value.
+    pub fn to_bits(&self) -> u64 {
+        self.0.to_bits()
+    }
+
+    /// Absolute value computed by clearing the sign bit directly, avoiding the
+    /// `integer_decode` round-trip and preserving NaN payloads.
+    pub fn abs(&self) -> Self {
+        OrderedFloat(f64::from_bits(self.0.to_bits() & !SIGN_MASK))
+    }
+
+    /// Negation computed by toggling the sign bit directly, preserving NaN
+    /// payloads (unlike arithmetic `Neg`).
+    pub fn neg_bits(&self) -> Self {
+        OrderedFloat(f64::from_bits(self.0.to_bits() ^ SIGN_MASK))
+    }
+}
+
+impl OrderedFloat<f32> {
+    /// Maps this value to a monotonic `u32` whose natural unsigned `<` ordering
+    /// is consistent with the total order defined by this crate: a value that
+    /// `Ord` ranks as less always gets a smaller key.
+    ///
+    /// This is the standard IEEE-754 total-order bit trick, suitable for
+    /// radix-sorting floats or for storing them as big-endian byte keys in a
+    /// key-value store. Because this crate sorts *every* NaN as *greatest*,
+    /// regardless of sign, the NaN sign bit is normalized away before encoding
+    /// so that all NaN bit patterns map to the top of the `u32` range.
+    ///
+    /// The key defines a *finer* order than [`Ord`](#impl-Ord): it is a total
+    /// order over raw bit patterns, so it distinguishes values that the crate
+    /// treats as equal. `-0.0` and `+0.0` get different keys, and distinct NaN
+    /// payloads get distinct keys, even though `Ord` ranks each such pair as
+    /// `Equal`. Normalize the input first if you need equal values to produce
+    /// equal keys.
+    pub fn total_cmp_key(&self) -> u32 {
+        // All NaN sort as greatest, so strip the sign bit to keep negative NaN
+        // out of the bottom of the range.
+        let bits = if self.0.is_nan() {
+            self.0.to_bits() & !SIGN_MASK_F32
+        } else {
+            self.0.to_bits()
+        };
+        if bits & SIGN_MASK_F32 != 0 {
+            !bits
+        } else {
+            bits | SIGN_MASK_F32
+        }
+    }
+
+    /// Reconstructs the value from a key produced by
+    /// [`total_cmp_key`](#method.total_cmp_key).
+    pub fn from_total_cmp_key(key: u32) -> Self {
+        let bits = if key & SIGN_MASK_F32 != 0 {
+            key & !SIGN_MASK_F32
+        } else {
+            !key
+        };
+        OrderedFloat(f32::from_bits(bits))
+    }
+
+    /// Constructs a value directly from its raw IEEE-754 bits, preserving any
+    /// NaN payload.
+    pub fn from_bits(bits: u32) -> Self {
+        OrderedFloat(f32::from_bits(bits))
+    }
+
+    /// Returns the raw IEEE-754 bits of the wrapped value.
+    pub fn to_bits(&self) -> u32 {
+        self.0.to_bits()
+    }
+
+    /// Absolute value computed by clearing the sign bit directly, avoiding the
+    /// `integer_decode` round-trip and preserving NaN payloads.
+    pub fn abs(&self) -> Self {
+        OrderedFloat(f32::from_bits(self.0.to_bits() & !SIGN_MASK_F32))
+    }
+
+    /// Negation computed by toggling the sign bit directly, preserving NaN
+    /// payloads (unlike arithmetic `Neg`).
+    pub fn neg_bits(&self) -> Self {
+        OrderedFloat(f32::from_bits(self.0.to_bits() ^ SIGN_MASK_F32))
+    }
+}
+
 impl<T: Float> AsRef<T> for OrderedFloat<T> {
     fn as_ref(&self) -> &T {
         let OrderedFloat(ref val) = *self;
@@ -121,6 +270,16 @@ impl<T: Float> From<T> for OrderedFloat<T> {
     }
 }
 
+/// Parses a float directly into an `OrderedFloat`, forwarding to the inner
+/// `FromStr` (NaN is a valid `OrderedFloat`, so no extra check is needed).
+impl<T: Float + FromStr> FromStr for OrderedFloat<T> {
+    type Err = T::Err;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        T::from_str(src).map(OrderedFloat)
+    }
+}
+
 impl<T: Float> Deref for OrderedFloat<T> {
     type Target = T;
 
@@ -176,6 +335,27 @@ impl<T: Float> AsRef<T> for NotNaN<T> {
     }
 }
 
+/// `NotNaN` compares exactly like the wrapped value: its `PartialEq` and
+/// `PartialOrd` are derived from `T`'s, and its `Ord` follows `PartialOrd`, so
+/// the borrowed `T` agrees with the wrapper on equality and ordering. Its
+/// `Hash` does not call a `Hash` impl of `T`; it hashes the value's raw bits,
+/// with `-0.0` and `+0.0` mapped to one pattern.
+///
+/// Note that this does *not* let you look up a `HashMap<NotNaN<f64>, V>` or
+/// `BTreeMap<NotNaN<f64>, V>` with a bare `&f64`: the built-in `f32`/`f64`
+/// types implement neither `Ord` nor `Hash` + `Eq`, so `map.get::<f64>(..)`
+/// does not satisfy the collections' own bounds. A lookup by `&T` compiles
+/// only for custom `Float` types that *do* implement those traits.
+///
+/// `OrderedFloat` intentionally does *not* offer this, because its
+/// NaN-normalizing `Hash`/`Eq`/`Ord` diverge from those of the inner `T`, which
+/// would violate the `Borrow` contract.
+impl<T: Float> Borrow<T> for NotNaN<T> {
+    fn borrow(&self) -> &T {
+        self.as_ref()
+    }
+}
+
 impl<T: Float + PartialOrd> Ord for NotNaN<T> {
     fn cmp(&self, other: &NotNaN<T>) -> Ordering {
         match self.partial_cmp(&other) {
@@ -516,10 +696,71 @@ impl<T: Float> Neg for NotNaN<T> {
     }
 }
 
+/// Sums the items of an iterator, panicking if the running total ever becomes
+/// NaN (for example `+inf` added to `-inf`).
+impl Sum for NotNaN<f64> {
+    fn sum<I: Iterator<Item = NotNaN<f64>>>(iter: I) -> Self {
+        iter.fold(NotNaN(0.0f64), |a, b| {
+            NotNaN::new(a.0 + b.0).expect("Addition resulted in NaN")
+        })
+    }
+}
+
+impl<'a> Sum<&'a NotNaN<f64>> for NotNaN<f64> {
+    fn sum<I: Iterator<Item = &'a NotNaN<f64>>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// Sums the items of an iterator, panicking if the running total ever becomes
+/// NaN (for example `+inf` added to `-inf`).
+impl Sum for NotNaN<f32> {
+    fn sum<I: Iterator<Item = NotNaN<f32>>>(iter: I) -> Self {
+        iter.fold(NotNaN(0.0f32), |a, b| {
+            NotNaN::new(a.0 + b.0).expect("Addition resulted in NaN")
+        })
+    }
+}
+
+impl<'a> Sum<&'a NotNaN<f32>> for NotNaN<f32> {
+    fn sum<I: Iterator<Item = &'a NotNaN<f32>>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// Multiplies the items of an iterator, panicking if the running product ever
+/// becomes NaN (for example `0` times `inf`).
+impl Product for NotNaN<f64> {
+    fn product<I: Iterator<Item = NotNaN<f64>>>(iter: I) -> Self {
+        iter.fold(NotNaN(1.0f64), Mul::mul)
+    }
+}
+
+impl<'a> Product<&'a NotNaN<f64>> for NotNaN<f64> {
+    fn product<I: Iterator<Item = &'a NotNaN<f64>>>(iter: I) -> Self {
+        iter.cloned().product()
+    }
+}
+
+/// Multiplies the items of an iterator, panicking if the running product ever
+/// becomes NaN (for example `0` times `inf`).
+impl Product for NotNaN<f32> {
+    fn product<I: Iterator<Item = NotNaN<f32>>>(iter: I) -> Self {
+        iter.fold(NotNaN(1.0f32), Mul::mul)
+    }
+}
+
+impl<'a> Product<&'a NotNaN<f32>> for NotNaN<f32> {
+    fn product<I: Iterator<Item = &'a NotNaN<f32>>>(iter: I) -> Self {
+        iter.cloned().product()
+    }
+}
+
 /// An error indicating an attempt to construct NotNaN from a NaN
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub struct FloatIsNaN;
 
+#[cfg(feature = "std")]
 impl Error for FloatIsNaN {
     fn description(&self) -> &str {
         return "NotNaN constructed with NaN";
@@ -532,6 +773,7 @@ impl fmt::Display for FloatIsNaN {
     }
 }
 
+#[cfg(feature = "std")]
 impl Into<io::Error> for FloatIsNaN {
     fn into(self) -> io::Error {
         io::Error::new(io::ErrorKind::InvalidInput, self)
@@ -554,7 +796,7 @@ fn raw_double_bits<F: Float>(f: &F) -> u64 {
         return CANONICAL_ZERO_BITS;
     }
 
-    let exp_u64 = unsafe { mem::transmute::<i16, u16>(exp) } as u64;
+    let exp_u64 = (exp as u16) as u64;
     let sign_u64 = if sign > 0 { 1u64 } else { 0u64 };
     (man & MAN_MASK) | ((exp_u64 << 52) & EXP_MASK) | ((sign_u64 << 63) & SIGN_MASK)
 }
@@ -620,6 +862,7 @@ pub enum ParseNotNaNError<E> {
     IsNaN,
 }
 
+#[cfg(feature = "std")]
 impl<E: fmt::Debug> Error for ParseNotNaNError<E> {
     fn description(&self) -> &str {
         return "Error parsing a not-NaN floating point value";
@@ -632,6 +875,18 @@ impl<E: fmt::Debug> fmt::Display for ParseNotNaNError<E> {
     }
 }
 
+/// Parses a float and rejects NaN, reusing the `ParseNotNaNError` machinery
+/// already used by `Num::from_str_radix`.
+impl<T: Float + FromStr> FromStr for NotNaN<T> {
+    type Err = ParseNotNaNError<T::Err>;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        src.parse()
+            .map_err(ParseNotNaNError::ParseFloatError)
+            .and_then(|f| NotNaN::new(f).map_err(|_| ParseNotNaNError::IsNaN))
+    }
+}
+
 impl<T: Float + Num> Num for NotNaN<T> {
     type FromStrRadixErr = ParseNotNaNError<T::FromStrRadixErr>;
 
@@ -642,11 +897,14 @@ impl<T: Float + Num> Num for NotNaN<T> {
     }
 }
 
+// `abs` and `signum` resolve to the by-value methods of `Float` (`FloatCore`
+// without `std`) and the rest to `Signed`; all are available in `no_std`, so
+// this impl is not tied to `std`.
 impl<T: Float + Signed> Signed for NotNaN<T> {
     fn abs(&self) -> Self { NotNaN(self.0.abs()) }
 
     fn abs_sub(&self, other: &Self) -> Self {
-        NotNaN::new(self.0.abs_sub(other.0)).expect("Subtraction resulted in NaN")
+        NotNaN::new(Signed::abs_sub(&self.0, &other.0)).expect("Subtraction resulted in NaN")
     }
 
     fn signum(&self) -> Self { NotNaN(self.0.signum()) }
@@ -654,6 +912,96 @@ impl<T: Float + Signed> Signed for NotNaN<T> {
     fn is_negative(&self) -> bool { self.0.is_negative() }
 }
 
+/// A precision-tagged ordered float that can hold either single- or
+/// double-precision values while still being totally ordered, hashable and
+/// comparable *across* widths.
+///
+/// This is useful for canonical/serialization formats (such as the Preserves
+/// data model) that store `f32` and `f64` as distinct value kinds yet require a
+/// single deterministic ordering over all of them. The total order promotes
+/// `f32` to `f64` for the numeric comparison, breaks exact ties by ranking
+/// `Single` before `Double`, and — like [`OrderedFloat`] — sorts NaN as
+/// greatest and treats `-0.0` as equal to `0.0`. NaNs are normalized to a
+/// single canonical bit pattern per width, so hashing is stable.
+#[derive(Debug, Clone, Copy)]
+pub enum AnyOrderedFloat {
+    /// A single-precision value.
+    Single(OrderedFloat<f32>),
+    /// A double-precision value.
+    Double(OrderedFloat<f64>),
+}
+
+impl AnyOrderedFloat {
+    /// The value promoted to `f64` for cross-width numeric comparison.
+    fn as_f64(&self) -> f64 {
+        match *self {
+            AnyOrderedFloat::Single(f) => f.into_inner() as f64,
+            AnyOrderedFloat::Double(f) => f.into_inner(),
+        }
+    }
+
+    /// Tie-break rank: `Single` sorts before `Double` at equal numeric value.
+    fn width_rank(&self) -> u8 {
+        match *self {
+            AnyOrderedFloat::Single(_) => 0,
+            AnyOrderedFloat::Double(_) => 1,
+        }
+    }
+}
+
+impl From<OrderedFloat<f32>> for AnyOrderedFloat {
+    fn from(f: OrderedFloat<f32>) -> Self {
+        AnyOrderedFloat::Single(f)
+    }
+}
+
+impl From<OrderedFloat<f64>> for AnyOrderedFloat {
+    fn from(f: OrderedFloat<f64>) -> Self {
+        AnyOrderedFloat::Double(f)
+    }
+}
+
+impl Ord for AnyOrderedFloat {
+    fn cmp(&self, other: &AnyOrderedFloat) -> Ordering {
+        // Compare numerically via the `f64` total order, then break ties by
+        // width so the ordering is a strict total order across precisions.
+        OrderedFloat(self.as_f64())
+            .cmp(&OrderedFloat(other.as_f64()))
+            .then(self.width_rank().cmp(&other.width_rank()))
+    }
+}
+
+impl PartialOrd for AnyOrderedFloat {
+    fn partial_cmp(&self, other: &AnyOrderedFloat) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for AnyOrderedFloat {
+    fn eq(&self, other: &AnyOrderedFloat) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for AnyOrderedFloat {}
+
+impl Hash for AnyOrderedFloat {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash the width tag alongside the (NaN-normalized) value so that the
+        // `Eq`/`Hash` contract holds: equal values hash identically.
+        match *self {
+            AnyOrderedFloat::Single(f) => {
+                0u8.hash(state);
+                f.hash(state);
+            }
+            AnyOrderedFloat::Double(f) => {
+                1u8.hash(state);
+                f.hash(state);
+            }
+        }
+    }
+}
+
 #[cfg(feature = "serde")]
 mod impl_serde {
     extern crate serde;
@@ -714,3 +1062,268 @@ mod impl_serde {
             "invalid value: floating point `NaN`, expected float (but not NaN)");
     }
 }
+
+#[cfg(test)]
+mod borrow_tests {
+    use super::NotNaN;
+    use core::borrow::Borrow;
+
+    #[test]
+    fn borrow_yields_inner() {
+        let key = NotNaN::new(3.14f64).unwrap();
+        let raw: &f64 = Borrow::borrow(&key);
+        assert_eq!(*raw, 3.14f64);
+    }
+
+    #[test]
+    fn borrow_matches_inner_for_every_key() {
+        // Borrowing a `NotNaN<f64>` as `&f64` hands back exactly the wrapped
+        // value. (Built-in floats implement neither `Ord` nor `Hash` + `Eq`, so
+        // this cannot be exercised through a real `BTreeMap`/`HashMap` lookup by
+        // raw float; see the impl's documentation.)
+        for &v in &[1.0f64, 2.0, -0.5, 42.0] {
+            let key = NotNaN::new(v).unwrap();
+            let raw: &f64 = Borrow::borrow(&key);
+            assert_eq!(*raw, v);
+        }
+    }
+}
+
+#[cfg(test)]
+mod total_cmp_key_tests {
+    use super::OrderedFloat;
+    use core::f64;
+    use core::f32;
+
+    #[test]
+    fn f64_round_trip() {
+        let values = [
+            0.0f64,
+            -0.0f64,
+            f64::MIN_POSITIVE / 2.0, // subnormal
+            -(f64::MIN_POSITIVE / 2.0),
+            1.0,
+            -1.0,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+        ];
+        for &v in &values {
+            let key = OrderedFloat(v).total_cmp_key();
+            let back = OrderedFloat::<f64>::from_total_cmp_key(key);
+            assert_eq!(back.0.to_bits(), v.to_bits());
+        }
+    }
+
+    #[test]
+    fn f64_key_matches_total_order() {
+        // keys must sort in the same order the crate's `Ord` sorts the values
+        let mut sorted = [
+            f64::NEG_INFINITY,
+            -1.0,
+            -0.0,
+            0.0,
+            1.0,
+            f64::INFINITY,
+            // both signs of NaN rank as greatest, so both must encode above
+            // every finite value and `+inf`
+            -f64::NAN,
+            f64::NAN,
+        ];
+        sorted.sort_by(|a, b| OrderedFloat(*a).cmp(&OrderedFloat(*b)));
+        for pair in sorted.windows(2) {
+            let lo = OrderedFloat(pair[0]).total_cmp_key();
+            let hi = OrderedFloat(pair[1]).total_cmp_key();
+            assert!(lo <= hi, "{:?} !<= {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn f32_round_trip() {
+        let values = [
+            0.0f32,
+            -0.0f32,
+            f32::MIN_POSITIVE / 2.0, // subnormal
+            -(f32::MIN_POSITIVE / 2.0),
+            1.0,
+            -1.0,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NAN,
+        ];
+        for &v in &values {
+            let key = OrderedFloat(v).total_cmp_key();
+            let back = OrderedFloat::<f32>::from_total_cmp_key(key);
+            assert_eq!(back.0.to_bits(), v.to_bits());
+        }
+    }
+}
+
+#[cfg(test)]
+mod raw_bits_tests {
+    use super::OrderedFloat;
+
+    #[test]
+    fn bits_round_trip_preserves_nan_payload() {
+        let payload = 0x7ff0_0000_0000_abcdu64; // a NaN with a non-canonical payload
+        let f = OrderedFloat::<f64>::from_bits(payload);
+        assert!(f.0.is_nan());
+        assert_eq!(f.to_bits(), payload);
+    }
+
+    #[test]
+    fn abs_and_neg_bits_f64() {
+        let x = OrderedFloat(-3.5f64);
+        assert_eq!(x.abs().0, 3.5f64);
+        assert_eq!(x.neg_bits().0, 3.5f64);
+        assert_eq!(OrderedFloat(3.5f64).neg_bits().0, -3.5f64);
+    }
+
+    #[test]
+    fn abs_and_neg_bits_f32() {
+        let x = OrderedFloat(-3.5f32);
+        assert_eq!(x.abs().0, 3.5f32);
+        assert_eq!(x.neg_bits().0, 3.5f32);
+        assert_eq!(OrderedFloat(3.5f32).neg_bits().0, -3.5f32);
+    }
+}
+
+#[cfg(test)]
+mod any_ordered_float_tests {
+    use super::{AnyOrderedFloat, OrderedFloat};
+    use core::f64;
+
+    fn single(v: f32) -> AnyOrderedFloat {
+        AnyOrderedFloat::Single(OrderedFloat(v))
+    }
+
+    fn double(v: f64) -> AnyOrderedFloat {
+        AnyOrderedFloat::Double(OrderedFloat(v))
+    }
+
+    #[test]
+    fn cross_width_total_order() {
+        let mut values = vec![
+            double(f64::NAN),
+            single(1.0),
+            double(1.0),
+            single(-2.0),
+            double(f64::INFINITY),
+            single(0.0),
+        ];
+        values.sort();
+
+        // -2.0 < 0.0 < 1.0 (single before double on tie) < +inf < NaN
+        assert_eq!(
+            values,
+            vec![
+                single(-2.0),
+                single(0.0),
+                single(1.0),
+                double(1.0),
+                double(f64::INFINITY),
+                double(f64::NAN),
+            ]
+        );
+    }
+
+    #[test]
+    fn equal_numeric_different_width_are_distinct() {
+        assert_ne!(single(1.0), double(1.0));
+        assert!(single(1.0) < double(1.0));
+    }
+
+    #[test]
+    fn zero_signs_and_nan_are_canonical() {
+        assert_eq!(single(0.0), single(-0.0));
+        assert_eq!(double(f64::NAN), double(f64::NAN));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_matches_eq() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash(v: &AnyOrderedFloat) -> u64 {
+            let mut h = DefaultHasher::new();
+            v.hash(&mut h);
+            h.finish()
+        }
+
+        assert_eq!(hash(&single(0.0)), hash(&single(-0.0)));
+        assert_eq!(hash(&double(f64::NAN)), hash(&double(f64::NAN)));
+    }
+}
+
+#[cfg(test)]
+mod sum_product_tests {
+    use super::NotNaN;
+    use core::f64;
+
+    fn nn(v: f64) -> NotNaN<f64> {
+        NotNaN::new(v).unwrap()
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let values = [nn(1.0), nn(2.0), nn(3.0)];
+        assert_eq!(values.iter().cloned().sum::<NotNaN<f64>>(), nn(6.0));
+        assert_eq!(values.iter().cloned().product::<NotNaN<f64>>(), nn(6.0));
+    }
+
+    #[test]
+    fn sum_and_product_by_reference() {
+        let values = [nn(1.0), nn(2.0), nn(3.0)];
+        assert_eq!(values.iter().sum::<NotNaN<f64>>(), nn(6.0));
+        assert_eq!(values.iter().product::<NotNaN<f64>>(), nn(6.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "resulted in NaN")]
+    fn sum_panics_on_nan() {
+        let values = [nn(f64::INFINITY), nn(f64::NEG_INFINITY)];
+        let _: NotNaN<f64> = values.iter().cloned().sum();
+    }
+
+    #[test]
+    #[should_panic(expected = "resulted in NaN")]
+    fn product_panics_on_nan() {
+        let values = [nn(0.0), nn(f64::INFINITY)];
+        let _: NotNaN<f64> = values.iter().cloned().product();
+    }
+}
+
+#[cfg(test)]
+mod from_str_tests {
+    use super::{NotNaN, OrderedFloat, ParseNotNaNError};
+
+    #[test]
+    fn not_nan_parses_valid_float() {
+        let parsed: NotNaN<f64> = "3.14".parse().unwrap();
+        assert_eq!(parsed.into_inner(), 3.14);
+    }
+
+    #[test]
+    fn not_nan_rejects_nan() {
+        match "NaN".parse::<NotNaN<f64>>() {
+            Err(ParseNotNaNError::IsNaN) => {}
+            other => panic!("expected IsNaN, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn not_nan_forwards_parse_error() {
+        match "not a float".parse::<NotNaN<f64>>() {
+            Err(ParseNotNaNError::ParseFloatError(_)) => {}
+            other => panic!("expected ParseFloatError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ordered_float_parses_and_forwards_error() {
+        let parsed: OrderedFloat<f64> = "2.5".parse().unwrap();
+        assert_eq!(parsed.into_inner(), 2.5);
+        assert!("not a float".parse::<OrderedFloat<f64>>().is_err());
+    }
+}